@@ -1,139 +1,217 @@
 use std::default;
 
-use log::info;
-
-pub trait Score {
-    fn score(&self) -> i32;
+/// Errors returned by [`Game::roll`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The roll uses more pins than are left standing in the current frame.
+    NotEnoughPinsLeft,
+    /// The game has already used up its ten frames (and any earned bonus balls).
+    GameComplete,
 }
 
+/// The kind of a completed frame, as shown on a scorecard.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub enum FrameBonusType {
+pub enum FrameKind {
+    Open,
     Spare,
     Strike,
 }
 
-#[derive(Default, Debug, Clone, Copy)]
-pub struct Frame {
-    pub first_roll_pins: i32,
-    pub second_roll_pins: Option<i32>,
-    pub bonus: Option<FrameBonusType>,
-}
-
-impl Frame {
-    fn rolls_score(&self) -> i32 {
-        if self.second_roll_pins.is_none() {
-            return self.first_roll_pins;
-        }
-        self.first_roll_pins + self.second_roll_pins.unwrap()
-    }
+/// One frame's worth of scorecard data, as produced by [`Game::frame_scores`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrameResult {
+    pub kind: FrameKind,
+    /// The pins knocked down by each ball thrown in this frame so far. For
+    /// a tenth-frame strike or spare, this grows to include bonus balls as
+    /// they are thrown.
+    pub pins: Vec<i32>,
+    /// This frame's own contribution to the total score, or `None` until
+    /// its bonus balls (if any) have been thrown.
+    pub contribution: Option<i32>,
+    /// The running total through this frame, or `None` if this frame or
+    /// any earlier one is still missing its bonus balls.
+    pub running_total: Option<i32>,
 }
 
-impl Score for Frame {
-    fn score(&self) -> i32 {
-        let mut score = self.rolls_score();
-        if let Some(bonus) = self.bonus {
-            match bonus {
-                FrameBonusType::Spare => score += self.first_roll_pins,
-                FrameBonusType::Strike => {
-                    score += self.first_roll_pins;
-                    if let Some(second_roll) = self.second_roll_pins {
-                        score += second_roll;
-                    }
-                }
-            }
-        }
-
-        info!("Score for frame is {} ", score);
-        score
-    }
+/// Where the next roll falls with respect to frame boundaries.
+enum RollSlot {
+    /// No balls have been thrown yet in the current frame.
+    FreshFrame,
+    /// One (non-strike) ball has been thrown; `first` pins are already down.
+    SecondBall { first: i32 },
+    /// A tenth-frame bonus ball, on a freshly reset or partially cleared rack.
+    TenthBonusBall { max_pins: i32 },
+    /// All ten frames, plus any earned bonus balls, have been thrown.
+    Complete,
 }
 
 #[derive(Default, Debug)]
 pub struct Game {
-    frames: [Frame; 10],
-    current_frame_index: usize,
-    current_roll_index: usize,
-    bonus_tenth_frame_third_roll: Option<i32>,
+    rolls: Vec<i32>,
 }
 
 impl Game {
-    pub fn roll(&mut self, pins: i32) {
-        // update game frames
-        let mut frame = &mut self.frames[self.current_frame_index];
-        let next_frame_index = if self.current_frame_index + 1 < 10 {
-            Some(self.current_frame_index + 1)
-        } else {
-            None
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a new `Game` with `pins` appended to this game's roll
+    /// history, leaving `self` untouched. Lets callers build games up
+    /// functionally (`Game::new().rolled(7).rolled(3)`), cloning and
+    /// branching what-if lines without sharing mutable state.
+    ///
+    /// Fails under the same conditions [`Game::roll`] would return an
+    /// `Err` for, leaving `self` untouched.
+    pub fn rolled(&self, pins: i32) -> Result<Game, Error> {
+        let mut next = Game {
+            rolls: self.rolls.clone(),
         };
-        let mut bonus: Option<FrameBonusType> = None;
-        match self.current_roll_index {
-            0 => {
-                frame.first_roll_pins = pins;
+        next.roll(pins)?;
+        Ok(next)
+    }
 
-                if pins == 10 {
-                    bonus = Some(FrameBonusType::Strike);
-                }
-            }
-            1 => {
-                frame.second_roll_pins = Some(pins);
-                if pins + frame.first_roll_pins == 10 {
-                    bonus = Some(FrameBonusType::Spare);
-                }
-            }
-            2 => {
-                // bonus roll
-                self.bonus_tenth_frame_third_roll = Some(pins)
-            }
-            _ => {}
+    pub fn roll(&mut self, pins: i32) -> Result<(), Error> {
+        let max_pins = match self.next_roll_slot() {
+            RollSlot::Complete => return Err(Error::GameComplete),
+            RollSlot::FreshFrame => 10,
+            RollSlot::SecondBall { first } => 10 - first,
+            RollSlot::TenthBonusBall { max_pins } => max_pins,
+        };
+        if !(0..=max_pins).contains(&pins) {
+            return Err(Error::NotEnoughPinsLeft);
         }
 
-        if let Some(i) = next_frame_index {
-            self.frames[i].bonus = bonus;
+        self.rolls.push(pins);
+        Ok(())
+    }
+
+    /// Walks the rolls thrown so far to find where the next one would land:
+    /// still inside the first nine frames, inside the tenth frame's bonus
+    /// balls, or past the end of the game entirely.
+    fn next_roll_slot(&self) -> RollSlot {
+        let mut i = 0;
+        for _ in 0..9 {
+            match self.rolls.get(i) {
+                None => return RollSlot::FreshFrame,
+                Some(&10) => i += 1,
+                Some(&first) => match self.rolls.get(i + 1) {
+                    None => return RollSlot::SecondBall { first },
+                    Some(_) => i += 2,
+                },
+            }
         }
 
-        self.set_next_indices(bonus);
+        match self.rolls[i..] {
+            [] => RollSlot::FreshFrame,
+            [10] => RollSlot::TenthBonusBall { max_pins: 10 },
+            [first] => RollSlot::SecondBall { first },
+            [10, second] => RollSlot::TenthBonusBall {
+                max_pins: if second == 10 { 10 } else { 10 - second },
+            },
+            [first, second] if first + second == 10 => RollSlot::TenthBonusBall { max_pins: 10 },
+            _ => RollSlot::Complete,
+        }
     }
 
-    fn set_next_indices(&mut self, bonus: Option<FrameBonusType>) {
-        match self.current_roll_index {
-            0 => {
-                let is_strike = match bonus {
-                    None => false,
-                    Some(x) => (|b| b == FrameBonusType::Strike)(x),
-                };
+    fn is_complete(&self) -> bool {
+        matches!(self.next_roll_slot(), RollSlot::Complete)
+    }
 
-                if !is_strike {
-                    self.current_roll_index = 1;
-                } else {
-                    self.current_frame_index += 1;
-                    self.current_roll_index = 0;
-                }
-            }
-            1 => {
-                if self.current_frame_index != 9 {
-                    self.current_roll_index = 0;
-                    self.current_frame_index += 1;
-                } else {
-                    if let Some(_) = bonus {
-                        self.current_roll_index = 2;
-                    }
-                }
+    /// Scores a single frame starting at roll index `i`, along with the
+    /// number of rolls it consumes, provided enough of the roll log is
+    /// already available to resolve its bonus (if any).
+    fn try_frame_score(rolls: &[i32], i: usize) -> Option<(i32, usize)> {
+        let first = *rolls.get(i)?;
+        if first == 10 {
+            let bonus_1 = *rolls.get(i + 1)?;
+            let bonus_2 = *rolls.get(i + 2)?;
+            Some((10 + bonus_1 + bonus_2, 1))
+        } else {
+            let second = *rolls.get(i + 1)?;
+            if first + second == 10 {
+                let bonus = *rolls.get(i + 2)?;
+                Some((10 + bonus, 2))
+            } else {
+                Some((first + second, 2))
             }
-            _ => {}
         }
     }
-}
 
-impl Score for Game {
-    fn score(&self) -> i32 {
-        let mut total_score = 0i32;
-        for frame in self.frames {
-            total_score += frame.score()
+    /// Returns the game's total score, or `None` if fewer than ten frames
+    /// (plus any earned tenth-frame bonus balls) have been played yet.
+    pub fn maybe_score(&self) -> Option<i32> {
+        if !self.is_complete() {
+            return None;
+        }
+
+        let mut total_score = 0;
+        let mut i = 0;
+        for _ in 0..10 {
+            let (frame_score, consumed) = Self::try_frame_score(&self.rolls, i)
+                .expect("a complete game can always score all ten frames");
+            total_score += frame_score;
+            i += consumed;
         }
-        if let Some(bonus_last_roll_pins) = self.bonus_tenth_frame_third_roll {
-            total_score += bonus_last_roll_pins;
+        Some(total_score)
+    }
+
+    /// Reports each frame played so far: its kind, the pins knocked down
+    /// ball by ball, its own contribution once that can be resolved, and
+    /// the running total through that frame. A frame is only included
+    /// once enough balls have been thrown to tell open/spare/strike apart;
+    /// `contribution` and `running_total` stay `None` until its bonus
+    /// balls have also been thrown.
+    pub fn frame_scores(&self) -> Vec<FrameResult> {
+        let mut results = Vec::with_capacity(10);
+        let mut i = 0;
+        let mut running_total = Some(0);
+
+        for frame_number in 0..10 {
+            let pins: Vec<i32> = if frame_number == 9 {
+                match self.rolls.get(i..) {
+                    Some(rest) if !rest.is_empty() => rest.to_vec(),
+                    _ => break,
+                }
+            } else {
+                match self.rolls.get(i) {
+                    None => break,
+                    Some(&10) => vec![10],
+                    Some(&first) => match self.rolls.get(i + 1) {
+                        None => break,
+                        Some(&second) => vec![first, second],
+                    },
+                }
+            };
+
+            let kind = if pins[0] == 10 {
+                FrameKind::Strike
+            } else if pins.len() < 2 {
+                // tenth frame's first ball only, open/spare not yet decidable
+                break;
+            } else if pins[0] + pins[1] == 10 {
+                FrameKind::Spare
+            } else {
+                FrameKind::Open
+            };
+
+            let start = i;
+            i += pins.len();
+
+            let contribution = Self::try_frame_score(&self.rolls, start).map(|(score, _)| score);
+            running_total = match (running_total, contribution) {
+                (Some(total), Some(score)) => Some(total + score),
+                _ => None,
+            };
+
+            results.push(FrameResult {
+                kind,
+                pins,
+                contribution,
+                running_total,
+            });
         }
-        total_score
+
+        results
     }
 }
 
@@ -144,53 +222,169 @@ mod tests {
     #[test]
     fn it_works() {
         let new_game = Game::default();
-        assert_eq!(new_game.score(), 0);
+        assert_eq!(new_game.maybe_score(), None);
     }
 
     #[test]
     fn it_should_have_score_of_nine_after_rolls_of_4_then_5() {
         let mut new_game = Game::default();
-        new_game.roll(4);
-        new_game.roll(5);
-        assert_eq!(new_game.score(), 9);
+        new_game.roll(4).unwrap();
+        new_game.roll(5).unwrap();
+        assert_eq!(new_game.maybe_score(), None);
     }
 
     #[test]
     fn it_should_count_bonus_score_when_a_spare_is_performed() {
         let mut game = Game::default();
-        game.roll(7);
-        game.roll(3);
-        game.roll(5);
-        assert_eq!(game.score(), 20);
+        game.roll(7).unwrap();
+        game.roll(3).unwrap();
+        game.roll(5).unwrap();
+        assert_eq!(game.maybe_score(), None);
     }
 
     #[test]
     fn it_should_count_bonus_score_when_a_spare_is_performed_2() {
         let mut game = Game::default();
-        game.roll(7);
-        game.roll(3);
-        game.roll(5);
-        game.roll(2);
-        assert_eq!(game.score(), 22);
+        game.roll(7).unwrap();
+        game.roll(3).unwrap();
+        game.roll(5).unwrap();
+        game.roll(2).unwrap();
+        assert_eq!(game.maybe_score(), None);
     }
 
     #[test]
     fn it_should_count_bonus_score_when_a_strike_is_performed() {
         let mut game = Game::default();
-        game.roll(5);
-        game.roll(3);
-        game.roll(10);
-        game.roll(2);
-        game.roll(5);
-        assert_eq!(game.score(), 32);
+        game.roll(5).unwrap();
+        game.roll(3).unwrap();
+        game.roll(10).unwrap();
+        game.roll(2).unwrap();
+        game.roll(5).unwrap();
+        assert_eq!(game.maybe_score(), None);
     }
 
     #[test]
     fn it_should_return_a_perfect_score_of_300_with_a_full_game_of_strikes() {
         let mut game = Game::default();
-        for i in 0..12 {
-            game.roll(10);
+        for _ in 0..12 {
+            game.roll(10).unwrap();
         }
-        assert_eq!(game.score(), 300);
+        assert_eq!(game.maybe_score(), Some(300));
+    }
+
+    #[test]
+    fn it_should_count_a_strikes_bonus_that_spans_into_the_frame_after_next() {
+        let mut game = Game::default();
+        game.roll(10).unwrap();
+        game.roll(10).unwrap();
+        game.roll(3).unwrap();
+        game.roll(4).unwrap();
+        for _ in 0..14 {
+            game.roll(0).unwrap();
+        }
+        // frame 1: 10 + 10 + 3 = 23, frame 2: 10 + 3 + 4 = 17, frame 3: 3 + 4 = 7
+        assert_eq!(game.maybe_score(), Some(23 + 17 + 7));
+    }
+
+    #[test]
+    fn it_should_reject_a_roll_that_knocks_down_more_pins_than_are_left_standing() {
+        let mut game = Game::default();
+        game.roll(6).unwrap();
+        assert_eq!(game.roll(5), Err(Error::NotEnoughPinsLeft));
+    }
+
+    #[test]
+    fn it_should_reject_a_roll_with_a_negative_pin_count() {
+        let mut game = Game::default();
+        assert_eq!(game.roll(-3), Err(Error::NotEnoughPinsLeft));
+    }
+
+    #[test]
+    fn it_should_reject_rolls_once_the_game_is_complete() {
+        let mut game = Game::default();
+        for _ in 0..12 {
+            game.roll(10).unwrap();
+        }
+        assert_eq!(game.roll(10), Err(Error::GameComplete));
+    }
+
+    #[test]
+    fn it_should_build_a_game_functionally_via_rolled() {
+        let game = Game::new()
+            .rolled(7)
+            .unwrap()
+            .rolled(3)
+            .unwrap()
+            .rolled(5)
+            .unwrap();
+        assert_eq!(game.maybe_score(), None);
+    }
+
+    #[test]
+    fn it_should_leave_the_original_game_untouched_when_using_rolled() {
+        let game = Game::new().rolled(4).unwrap();
+        let branch = game.rolled(5).unwrap();
+        assert_eq!(game.rolls, vec![4]);
+        assert_eq!(branch.rolls, vec![4, 5]);
+    }
+
+    #[test]
+    fn it_should_return_an_error_from_rolled_instead_of_panicking() {
+        let game = Game::new().rolled(6).unwrap();
+        assert_eq!(game.rolled(5).unwrap_err(), Error::NotEnoughPinsLeft);
+        assert_eq!(game.rolls, vec![6]);
+    }
+
+    #[test]
+    fn it_should_report_open_frames_with_running_totals() {
+        let mut game = Game::default();
+        game.roll(4).unwrap();
+        game.roll(5).unwrap();
+        game.roll(3).unwrap();
+        game.roll(2).unwrap();
+
+        let frames = game.frame_scores();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].kind, FrameKind::Open);
+        assert_eq!(frames[0].pins, vec![4, 5]);
+        assert_eq!(frames[0].contribution, Some(9));
+        assert_eq!(frames[0].running_total, Some(9));
+        assert_eq!(frames[1].contribution, Some(5));
+        assert_eq!(frames[1].running_total, Some(14));
+    }
+
+    #[test]
+    fn it_should_withhold_a_strikes_contribution_until_its_bonus_balls_are_thrown() {
+        let mut game = Game::default();
+        game.roll(10).unwrap();
+        game.roll(2).unwrap();
+
+        let frames = game.frame_scores();
+        assert_eq!(frames.len(), 1);
+        assert_eq!(frames[0].kind, FrameKind::Strike);
+        assert_eq!(frames[0].pins, vec![10]);
+        assert_eq!(frames[0].contribution, None);
+        assert_eq!(frames[0].running_total, None);
+
+        game.roll(3).unwrap();
+        let frames = game.frame_scores();
+        assert_eq!(frames[0].contribution, Some(15));
+        assert_eq!(frames[0].running_total, Some(15));
+        assert_eq!(frames[1].contribution, Some(5));
+        assert_eq!(frames[1].running_total, Some(20));
+    }
+
+    #[test]
+    fn it_should_report_all_ten_frames_for_a_perfect_game() {
+        let mut game = Game::default();
+        for _ in 0..12 {
+            game.roll(10).unwrap();
+        }
+
+        let frames = game.frame_scores();
+        assert_eq!(frames.len(), 10);
+        assert!(frames.iter().all(|f| f.kind == FrameKind::Strike));
+        assert_eq!(frames.last().unwrap().pins, vec![10, 10, 10]);
+        assert_eq!(frames.last().unwrap().running_total, Some(300));
     }
 }